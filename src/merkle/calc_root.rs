@@ -27,17 +27,40 @@
 //!  /   \      /    \    /    \    /   \    /   \   /   \   /   \   /  \
 //! The quick brown fox jumps over the lazy dog  "" ""   "" ""   "" ""   ""
 //! 
-//! Notice each hash is a concatenation of the hashes of the blocks below it. 
-//! 
+//! Notice each hash is a concatenation of the hashes of the blocks below it.
+//!
+//! `calc_root` above is the padded, `u64`/`DefaultHasher` construction just
+//! described, but it isn't the only one in this module:
+//! - `calc_root_rfc6962` builds the root the RFC 6962 way instead, splitting
+//!   leaves at the largest power of two below their count rather than padding.
+//! - `calc_root_with_hasher` and `MerkleTree<H>` generify the digest over the
+//!   `MerkleHasher` trait (SHA-256, Keccak-256, BLAKE3, or the zero-dependency
+//!   `StdHasher`), and `MerkleTree::update_leaf` recomputes only the dirty
+//!   path after a single leaf changes instead of rebuilding from scratch.
+//! - `gen_proof` / `verify_proof` produce and check `MerkleProof`s showing a
+//!   word was included under a given root.
+//! - `calc_root_from_leaves` / `calc_root_from_leaf_vecs` accept arbitrary
+//!   byte-slice leaves instead of only whitespace-split words.
+//!
 
 use std::{hash::{self, Hasher}, collections::hash_map::DefaultHasher};
 use crate::merkle::calc_root::hash::Hash;
 
+use digest::Digest;
+use sha2::Sha256;
+use sha3::Keccak256;
+
 
 /// A hash value is a 64 bit unsigned integer.
 /// We could choose to use a u64, rather than a type, but this is more explicit and allows easier modifications.
 pub type HashValue = u64;
 
+/// Domain-separation prefixes, RFC 6962 style: leaves and internal nodes are
+/// hashed with distinct leading bytes so a node's two children can never be
+/// replayed as a leaf to forge an equivalent root (a second-preimage attack).
+pub const LEAF_PREFIX: u8 = 0x00;
+pub const NODE_PREFIX: u8 = 0x01;
+
 
 /// A merkle tree must be balanced, so we pad the base layer with empty strings
 /// More specifically, a merkle tree must be a power of 2.
@@ -58,27 +81,56 @@ pub fn hash<T: Hash>(t: &T) -> HashValue {
     s.finish()
 }
 
+/// Hashes a leaf as `LEAF_PREFIX || data`, so a leaf hash can never collide
+/// with a node hash produced by `concatenate_hash_values`. All leaf hashing
+/// should go through this function rather than the bare `hash`.
+pub fn hash_leaf<T: Hash>(t: &T) -> HashValue {
+    let mut s = DefaultHasher::new();
+    LEAF_PREFIX.hash(&mut s);
+    t.hash(&mut s);
+    s.finish()
+}
+
 /// This is where we concatenate the hash values.
 /// There are better ways to do this, but the purpose of this is to demonstrate the concept.
 pub fn concatenate_hash_values(left: HashValue, right: HashValue) -> HashValue {
     let left = left.to_le_bytes();
     let right = right.to_le_bytes();
 
-    let mut cmb: Vec<u8> = Vec::with_capacity(left.len() + right.len());
+    let mut cmb: Vec<u8> = Vec::with_capacity(1 + left.len() + right.len());
+    cmb.push(NODE_PREFIX);
     cmb.extend_from_slice(&left);
     cmb.extend_from_slice(&right);
     hash(&cmb)
 }
 
 
+/// A thin adapter over `calc_root_from_leaves`: splits `sentence` into
+/// whitespace-separated words and delegates to it, so English sentences stay
+/// the convenient entry point while other callers can feed arbitrary bytes.
 pub fn calc_root(sentence: &str) -> HashValue {
-    let mut leafs = sentence.split_whitespace().collect::<Vec<&str>>();
-    pad_base_layer(&mut leafs);
+    calc_root_from_leaves(sentence.split_whitespace().map(|word| word.as_bytes()))
+}
+
+/// Computes the root over an ordered sequence of byte-slice leaves, not just
+/// whitespace-split words, so binary blobs, pre-hashed leaves, or already
+/// tokenized data (e.g. a transaction list or erasure-coded shards) can be
+/// hashed directly.
+pub fn calc_root_from_leaves<'a, T: IntoIterator<Item = &'a [u8]>>(leaves: T) -> HashValue {
+    let mut leafs = leaves.into_iter().collect::<Vec<&[u8]>>();
+
+    if leafs.is_empty() {
+        return hash_leaf(&(b"" as &[u8]));
+    }
+
+    while leafs.len() & (leafs.len() - 1) != 0 {
+        leafs.push(&[]);
+    }
 
     let mut queue = leafs
         .iter()
         .rev()
-        .map(|x| hash(x))
+        .map(hash_leaf)
         .collect::<Vec<HashValue>>();
 
     while queue.len() > 1 {
@@ -90,6 +142,287 @@ pub fn calc_root(sentence: &str) -> HashValue {
     queue[0]
 }
 
+/// Convenience wrapper over `calc_root_from_leaves` for callers who already
+/// have their leaves as owned byte vectors.
+pub fn calc_root_from_leaf_vecs(leaves: &[Vec<u8>]) -> HashValue {
+    calc_root_from_leaves(leaves.iter().map(|leaf| leaf.as_slice()))
+}
+
+/// A proof that a single leaf was included in the tree that produced `root`.
+///
+/// `siblings` holds the hash at each level of the path from the leaf up to the
+/// root, ordered from the leaf's own level to the level just below the root.
+/// Unlike `calc_root`, `gen_proof` builds the tree one whole level at a time
+/// instead of collapsing a single queue as it goes, since each level
+/// contributes one sibling to the path; only that sibling is kept per level.
+pub struct MerkleProof {
+    pub leaf_index: usize,
+    pub siblings: Vec<HashValue>,
+    pub root: HashValue,
+}
+
+/// Builds a `MerkleProof` for the word at `index` in `sentence`.
+/// Returns `None` if `index` is out of bounds for the (unpadded) word count.
+pub fn gen_proof(sentence: &str, index: usize) -> Option<MerkleProof> {
+    let words = sentence.split_whitespace().collect::<Vec<&str>>();
+    if index >= words.len() {
+        return None;
+    }
+
+    let mut leafs = words;
+    pad_base_layer(&mut leafs);
+
+    let mut level = leafs.iter().map(|x| hash_leaf(&x.as_bytes())).collect::<Vec<HashValue>>();
+    let mut siblings = Vec::new();
+    let mut idx = index;
+
+    while level.len() > 1 {
+        siblings.push(level[idx ^ 1]);
+
+        level = level
+            .chunks(2)
+            .map(|pair| concatenate_hash_values(pair[0], pair[1]))
+            .collect();
+
+        idx /= 2;
+    }
+
+    Some(MerkleProof {
+        leaf_index: index,
+        siblings,
+        root: level[0],
+    })
+}
+
+/// Verifies that `leaf` was included at `proof.leaf_index` under `proof.root`.
+///
+/// Recomputes the path upward from `hash_leaf(leaf)`, using the low bit of the
+/// current index at each level to decide whether the stored sibling belongs
+/// on the left or the right, and compares the result against `proof.root`.
+pub fn verify_proof(leaf: &str, proof: &MerkleProof) -> bool {
+    let mut acc = hash_leaf(&leaf.as_bytes());
+    let mut idx = proof.leaf_index;
+
+    for sibling in &proof.siblings {
+        acc = if idx & 1 == 0 {
+            concatenate_hash_values(acc, *sibling)
+        } else {
+            concatenate_hash_values(*sibling, acc)
+        };
+        idx /= 2;
+    }
+
+    acc == proof.root
+}
+
+/// A pluggable digest for the tree, decoupling the Merkle structure from any
+/// one hash function so callers can pick a digest that other Merkle-tree
+/// tooling (Tendermint, Ethereum, ...) already speaks, instead of being stuck
+/// with the non-cryptographic `DefaultHasher`.
+///
+/// `hash_nodes`'s `left`/`right` are always this same implementation's own
+/// `hash_leaf`/`hash_nodes` output (that's how `MerkleTree` calls it), so an
+/// implementation may assume they are exactly its own digest length and is
+/// free to panic otherwise rather than handle arbitrary attacker-shaped input.
+pub trait MerkleHasher {
+    fn hash_leaf(&self, data: &[u8]) -> Vec<u8>;
+    fn hash_nodes(&self, left: &[u8], right: &[u8]) -> Vec<u8>;
+}
+
+/// The zero-dependency default: wraps the existing `DefaultHasher`-based
+/// `hash_leaf` / `concatenate_hash_values`, so callers who don't need
+/// interoperability with another toolchain can use `MerkleTree<StdHasher>`.
+/// `StdHasher` itself uses only the standard library; the SHA-256,
+/// Keccak-256 and BLAKE3 hashers below are still compiled in with this crate.
+pub struct StdHasher;
+
+impl MerkleHasher for StdHasher {
+    fn hash_leaf(&self, data: &[u8]) -> Vec<u8> {
+        hash_leaf(&data).to_le_bytes().to_vec()
+    }
+
+    /// Panics if `left` or `right` is not exactly 8 bytes, i.e. not a digest
+    /// this same `StdHasher` produced.
+    fn hash_nodes(&self, left: &[u8], right: &[u8]) -> Vec<u8> {
+        let left = HashValue::from_le_bytes(left.try_into().expect("StdHasher digests are 8 bytes"));
+        let right = HashValue::from_le_bytes(right.try_into().expect("StdHasher digests are 8 bytes"));
+        concatenate_hash_values(left, right).to_le_bytes().to_vec()
+    }
+}
+
+/// SHA-256, compatible with Tendermint-style Merkle trees.
+pub struct Sha256Hasher;
+
+impl MerkleHasher for Sha256Hasher {
+    fn hash_leaf(&self, data: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update([LEAF_PREFIX]);
+        hasher.update(data);
+        hasher.finalize().to_vec()
+    }
+
+    fn hash_nodes(&self, left: &[u8], right: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update([NODE_PREFIX]);
+        hasher.update(left);
+        hasher.update(right);
+        hasher.finalize().to_vec()
+    }
+}
+
+/// Keccak-256, compatible with Ethereum-style Merkle/Merkle-Patricia tooling.
+pub struct Keccak256Hasher;
+
+impl MerkleHasher for Keccak256Hasher {
+    fn hash_leaf(&self, data: &[u8]) -> Vec<u8> {
+        let mut hasher = Keccak256::new();
+        hasher.update([LEAF_PREFIX]);
+        hasher.update(data);
+        hasher.finalize().to_vec()
+    }
+
+    fn hash_nodes(&self, left: &[u8], right: &[u8]) -> Vec<u8> {
+        let mut hasher = Keccak256::new();
+        hasher.update([NODE_PREFIX]);
+        hasher.update(left);
+        hasher.update(right);
+        hasher.finalize().to_vec()
+    }
+}
+
+/// BLAKE3, for callers that want a fast, modern tree hash.
+pub struct Blake3Hasher;
+
+impl MerkleHasher for Blake3Hasher {
+    fn hash_leaf(&self, data: &[u8]) -> Vec<u8> {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&[LEAF_PREFIX]);
+        hasher.update(data);
+        hasher.finalize().as_bytes().to_vec()
+    }
+
+    fn hash_nodes(&self, left: &[u8], right: &[u8]) -> Vec<u8> {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&[NODE_PREFIX]);
+        hasher.update(left);
+        hasher.update(right);
+        hasher.finalize().as_bytes().to_vec()
+    }
+}
+
+/// A Merkle tree generic over its digest, retaining every level (rather than
+/// only the root) so higher-level operations like proof generation or
+/// incremental updates can walk back down the tree.
+pub struct MerkleTree<H: MerkleHasher = StdHasher> {
+    hasher: H,
+    levels: Vec<Vec<Vec<u8>>>,
+}
+
+impl<H: MerkleHasher> MerkleTree<H> {
+    /// Builds the tree for `sentence` under `hasher`, from the leaf level up.
+    pub fn new(sentence: &str, hasher: H) -> Self {
+        let mut leafs = sentence.split_whitespace().collect::<Vec<&str>>();
+
+        if leafs.is_empty() {
+            let levels = vec![vec![hasher.hash_leaf(b"")]];
+            return MerkleTree { hasher, levels };
+        }
+
+        pad_base_layer(&mut leafs);
+
+        let mut level: Vec<Vec<u8>> = leafs
+            .iter()
+            .map(|word| hasher.hash_leaf(word.as_bytes()))
+            .collect();
+        let mut levels = vec![level.clone()];
+
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| hasher.hash_nodes(&pair[0], &pair[1]))
+                .collect();
+            levels.push(level.clone());
+        }
+
+        MerkleTree { hasher, levels }
+    }
+
+    pub fn root_hash(&self) -> &[u8] {
+        &self.levels.last().expect("a tree always has at least one level")[0]
+    }
+
+    /// Re-hashes the leaf at `index` and recomputes only the dirty path up to
+    /// the root (sibling `i ^ 1` joins parent `i / 2` at every level), instead
+    /// of rebuilding the whole tree. The retained `levels` act as the cache:
+    /// only the nodes on this path are touched, mirroring the tree-hash
+    /// caching used in SSZ implementations.
+    ///
+    /// Returns `None`, leaving the tree unchanged, if `index` is out of
+    /// bounds for the (padded) leaf level, mirroring `gen_proof`'s bounds
+    /// check rather than panicking on a raw `Vec` index.
+    pub fn update_leaf(&mut self, index: usize, new_value: &str) -> Option<()> {
+        if index >= self.levels[0].len() {
+            return None;
+        }
+
+        let mut i = index;
+        self.levels[0][i] = self.hasher.hash_leaf(new_value.as_bytes());
+
+        for level in 1..self.levels.len() {
+            let parent = i / 2;
+            let sibling = i ^ 1;
+            let (left_idx, right_idx) = if i.is_multiple_of(2) { (i, sibling) } else { (sibling, i) };
+
+            let left = self.levels[level - 1][left_idx].clone();
+            let right = self.levels[level - 1][right_idx].clone();
+            self.levels[level][parent] = self.hasher.hash_nodes(&left, &right);
+
+            i = parent;
+        }
+
+        Some(())
+    }
+}
+
+/// The `MerkleHasher`-generic counterpart to `calc_root`, for callers who
+/// need a digest other than the built-in `u64` `DefaultHasher` scheme.
+pub fn calc_root_with_hasher<H: MerkleHasher>(sentence: &str, hasher: H) -> Vec<u8> {
+    MerkleTree::new(sentence, hasher).root_hash().to_vec()
+}
+
+/// The largest power of two strictly less than `n`. Only meaningful for `n > 1`.
+fn largest_power_of_two_less_than(n: usize) -> usize {
+    let mut k = 1;
+    while k * 2 < n {
+        k *= 2;
+    }
+    k
+}
+
+/// Computes the root the way RFC 6962 does: leaves are never padded. A single
+/// leaf hashes directly, an empty input is the hash of the empty slice, and
+/// otherwise the leaves are split at the largest power of two strictly below
+/// their count, each half is rooted recursively, and the two sub-roots are
+/// concatenated. This matches `calc_root` exactly when the leaf count is
+/// already a power of two, but avoids hashing dummy padding otherwise.
+pub fn calc_root_rfc6962(sentence: &str) -> HashValue {
+    let leafs = sentence.split_whitespace().collect::<Vec<&str>>();
+    calc_root_rfc6962_range(&leafs)
+}
+
+fn calc_root_rfc6962_range(leafs: &[&str]) -> HashValue {
+    match leafs.len() {
+        0 => hash_leaf(&(b"" as &[u8])),
+        1 => hash_leaf(&leafs[0].as_bytes()),
+        n => {
+            let k = largest_power_of_two_less_than(n);
+            let left = calc_root_rfc6962_range(&leafs[..k]);
+            let right = calc_root_rfc6962_range(&leafs[k..]);
+            concatenate_hash_values(left, right)
+        }
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -109,4 +442,126 @@ mod tests {
 
 
     }
+
+    #[test]
+    fn gen_proof_verifies_against_root() {
+        let data = "My name is Jeff";
+        let root = calc_root(data);
+
+        for (index, word) in data.split_whitespace().enumerate() {
+            let proof = gen_proof(data, index).unwrap();
+            assert_eq!(proof.root, root);
+            assert!(verify_proof(word, &proof));
+        }
+    }
+
+    #[test]
+    fn gen_proof_out_of_bounds_is_none() {
+        let data = "My name is Jeff";
+        assert!(gen_proof(data, 4).is_none());
+    }
+
+    #[test]
+    fn verify_proof_rejects_wrong_leaf() {
+        let data = "My name is Jeff";
+        let proof = gen_proof(data, 0).unwrap();
+        assert!(!verify_proof("Not", &proof));
+    }
+
+    #[test]
+    fn std_hasher_is_deterministic() {
+        let data = "My name is Jeff";
+
+        let first = calc_root_with_hasher(data, StdHasher);
+        let second = calc_root_with_hasher(data, StdHasher);
+
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 8);
+    }
+
+    #[test]
+    fn merkle_tree_new_empty_does_not_panic() {
+        let tree = MerkleTree::new("", StdHasher);
+        assert_eq!(tree.root_hash(), StdHasher.hash_leaf(b""));
+    }
+
+    #[test]
+    fn calc_root_with_hasher_empty_does_not_panic() {
+        assert_eq!(calc_root_with_hasher("", StdHasher), StdHasher.hash_leaf(b""));
+    }
+
+    #[test]
+    fn different_hashers_produce_different_roots() {
+        let data = "My name is Jeff";
+
+        let sha256_root = calc_root_with_hasher(data, Sha256Hasher);
+        let keccak_root = calc_root_with_hasher(data, Keccak256Hasher);
+        let blake3_root = calc_root_with_hasher(data, Blake3Hasher);
+
+        assert_ne!(sha256_root, keccak_root);
+        assert_ne!(sha256_root, blake3_root);
+        assert_ne!(keccak_root, blake3_root);
+    }
+
+    #[test]
+    fn rfc6962_matches_calc_root_when_balanced() {
+        let data = "My name is Jeff";
+        assert_eq!(calc_root_rfc6962(data), calc_root(data));
+    }
+
+    #[test]
+    fn rfc6962_differs_from_padded_calc_root_when_unbalanced() {
+        let data = "The quick brown fox jumps over the lazy dog";
+        assert_ne!(calc_root_rfc6962(data), calc_root(data));
+    }
+
+    #[test]
+    fn rfc6962_empty_input() {
+        assert_eq!(calc_root_rfc6962(""), hash_leaf(&(b"" as &[u8])));
+    }
+
+    #[test]
+    fn update_leaf_matches_full_rebuild() {
+        let mut tree = MerkleTree::new("My name is Jeff", StdHasher);
+        assert_eq!(tree.update_leaf(1, "nickname"), Some(()));
+
+        let rebuilt = MerkleTree::new("My nickname is Jeff", StdHasher);
+        assert_eq!(tree.root_hash(), rebuilt.root_hash());
+    }
+
+    #[test]
+    fn update_leaf_out_of_bounds_is_none() {
+        let mut tree = MerkleTree::new("My name is Jeff", StdHasher);
+        let root_before = tree.root_hash().to_vec();
+
+        assert_eq!(tree.update_leaf(4, "nope"), None);
+        assert_eq!(tree.root_hash(), root_before.as_slice());
+    }
+
+    #[test]
+    fn calc_root_from_leaves_matches_calc_root() {
+        let data = "My name is Jeff";
+        let leaves = data.split_whitespace().map(|word| word.as_bytes());
+
+        assert_eq!(calc_root_from_leaves(leaves), calc_root(data));
+    }
+
+    #[test]
+    fn calc_root_from_leaf_vecs_matches_calc_root_from_leaves() {
+        let data = "My name is Jeff";
+        let leaves = data
+            .split_whitespace()
+            .map(|word| word.as_bytes())
+            .collect::<Vec<&[u8]>>();
+        let owned_leaves = leaves.iter().map(|leaf| leaf.to_vec()).collect::<Vec<Vec<u8>>>();
+
+        assert_eq!(calc_root_from_leaf_vecs(&owned_leaves), calc_root_from_leaves(leaves));
+    }
+
+    #[test]
+    fn calc_root_from_leaves_empty_does_not_panic() {
+        let leaves: Vec<&[u8]> = Vec::new();
+        assert_eq!(calc_root_from_leaves(leaves), hash_leaf(&(b"" as &[u8])));
+        assert_eq!(calc_root(""), hash_leaf(&(b"" as &[u8])));
+    }
 }
\ No newline at end of file
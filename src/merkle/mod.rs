@@ -0,0 +1 @@
+pub mod calc_root;